@@ -0,0 +1,20 @@
+use crate::query::Scorer;
+use crate::Score;
+
+/// A [`Scorer`] that can bound its own score from above, both globally and
+/// within the current block of postings.
+///
+/// This is the hook WAND/MaxScore-style dynamic pruning is built on:
+/// `max_score()` lets a union scorer decide which terms are "essential" for
+/// the current threshold, and `block_max_score()` lets it tighten that
+/// bound as it walks through blocks of postings, without needing to know
+/// anything about the scoring model producing the bound.
+pub trait BlockMaxScorer: Scorer {
+    /// Upper bound on this scorer's `score()`, over every document it could
+    /// ever be positioned on.
+    fn max_score(&self) -> Score;
+
+    /// Upper bound on this scorer's `score()`, restricted to the block of
+    /// postings it is currently positioned in. Always `<= max_score()`.
+    fn block_max_score(&mut self) -> Score;
+}
@@ -0,0 +1,93 @@
+use crate::fieldnorm::FieldNormReader;
+use crate::query::similarity::SimilarityWeight;
+use crate::query::Explanation;
+use crate::Score;
+
+const K1: Score = 1.2;
+const B: Score = 0.75;
+
+/// Computes the classic BM25 IDF, with the usual `+ 0.5` smoothing to avoid
+/// negative or zero values for terms that appear in (almost) every
+/// document.
+pub fn idf(doc_freq: u64, doc_count: u64) -> Score {
+    let x = ((doc_count - doc_freq) as Score + 0.5) / (doc_freq as Score + 0.5);
+    (1.0 + x).ln()
+}
+
+fn cached_tf_component(fieldnorm: u32, average_fieldnorm: Score) -> Score {
+    K1 * (1.0 - B + B * fieldnorm as Score / average_fieldnorm)
+}
+
+fn compute_tf_cache(average_fieldnorm: Score) -> [Score; 256] {
+    let mut cache = [0f32; 256];
+    for (fieldnorm_id, cache_mut) in cache.iter_mut().enumerate() {
+        let fieldnorm = FieldNormReader::id_to_fieldnorm(fieldnorm_id as u8);
+        *cache_mut = cached_tf_component(fieldnorm, average_fieldnorm);
+    }
+    cache
+}
+
+/// BM25 similarity weight for a single term, pre-computed once per segment.
+///
+/// This is the default [`SimilarityWeight`](crate::query::SimilarityWeight)
+/// implementation used by [`TermScorer`](crate::query::term_query::TermScorer).
+#[derive(Clone)]
+pub struct BM25Weight {
+    weight: Score,
+    cache: [Score; 256],
+}
+
+impl BM25Weight {
+    pub(crate) fn for_one_term(
+        term_doc_freq: u64,
+        total_num_docs: u64,
+        average_fieldnorm: Score,
+    ) -> BM25Weight {
+        let idf = idf(term_doc_freq, total_num_docs);
+        BM25Weight::new(idf, average_fieldnorm)
+    }
+
+    fn new(idf: Score, average_fieldnorm: Score) -> BM25Weight {
+        BM25Weight {
+            weight: idf * (1.0 + K1),
+            cache: compute_tf_cache(average_fieldnorm),
+        }
+    }
+
+    pub fn boost_by(&self, boost: Score) -> BM25Weight {
+        BM25Weight {
+            weight: self.weight * boost,
+            cache: self.cache,
+        }
+    }
+
+    fn tf_factor(&self, fieldnorm_id: u8, term_freq: u32) -> Score {
+        let term_freq = term_freq as Score;
+        let norm = self.cache[fieldnorm_id as usize];
+        term_freq / (term_freq + norm)
+    }
+}
+
+impl SimilarityWeight for BM25Weight {
+    fn score(&self, fieldnorm_id: u8, term_freq: u32) -> Score {
+        self.weight * self.tf_factor(fieldnorm_id, term_freq)
+    }
+
+    fn max_score(&self) -> Score {
+        self.weight
+    }
+
+    fn explain(&self, fieldnorm_id: u8, term_freq: u32) -> Explanation {
+        let tf_factor = self.tf_factor(fieldnorm_id, term_freq);
+        let mut explanation = Explanation::new(
+            "TermQuery, product of...",
+            self.weight * tf_factor,
+        );
+        explanation.add_detail(Explanation::new("weight(idf * (1 + k1))", self.weight));
+        explanation.add_detail(Explanation::new(
+            "tfFactor(term_freq, fieldnorm)",
+            tf_factor,
+        ));
+        explanation
+    }
+}
@@ -0,0 +1,65 @@
+use crate::query::bm25::idf;
+use crate::query::Explanation;
+use crate::Score;
+
+const K1: Score = 1.2;
+
+/// Per-field parameters used to fold a field's term frequency into the
+/// combined pseudo term frequency of a [`BM25FWeight`].
+#[derive(Clone, Copy)]
+pub struct BM25FFieldParams {
+    /// Static boost applied to this field's contribution (e.g. titles
+    /// typically outweigh body text).
+    pub boost: Score,
+    /// Length-normalization factor for this field, analogous to BM25's `b`.
+    pub b: Score,
+    /// Average fieldnorm for this field, across the collection.
+    pub average_fieldnorm: Score,
+}
+
+/// BM25F similarity weight: scores a single term across several fields of
+/// the same document jointly, instead of scoring each field independently
+/// and summing the results (which double-counts saturation and ignores
+/// per-field length).
+///
+/// Unlike [`BM25Weight`](crate::query::BM25Weight), this weight does not
+/// carry the per-field parameters itself: `BM25FScorer` bundles each
+/// field's [`BM25FFieldParams`] together with that field's postings, so
+/// there is exactly one place a field's config and its postings can get
+/// out of sync (nowhere). This weight only holds the term-level IDF, and
+/// `score`/`explain` take the already-combined pseudo term frequency:
+///
+/// ```text
+/// tilde_tf = sum_f boost_f * tf_f / (1 - b_f + b_f * len_f / avglen_f)
+/// score = idf * tilde_tf / (k1 + tilde_tf)
+/// ```
+#[derive(Clone)]
+pub struct BM25FWeight {
+    idf: Score,
+}
+
+impl BM25FWeight {
+    pub fn for_terms(term_doc_freq: u64, total_num_docs: u64) -> BM25FWeight {
+        BM25FWeight {
+            idf: idf(term_doc_freq, total_num_docs),
+        }
+    }
+
+    pub fn score(&self, pseudo_term_freq: Score) -> Score {
+        self.idf * pseudo_term_freq / (K1 + pseudo_term_freq)
+    }
+
+    /// Upper bound on `score()`, reached as the pseudo term frequency grows
+    /// unbounded.
+    pub fn max_score(&self) -> Score {
+        self.idf
+    }
+
+    pub fn explain(&self, pseudo_term_freq: Score) -> Explanation {
+        let score = self.score(pseudo_term_freq);
+        let mut explanation = Explanation::new("BM25F, product of...", score);
+        explanation.add_detail(Explanation::new("idf", self.idf));
+        explanation.add_detail(Explanation::new("pseudoTermFreq (tilde_tf)", pseudo_term_freq));
+        explanation
+    }
+}
@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use crate::docset::DocSet;
+use crate::fastfield::Column;
+use crate::query::{BlockMaxScorer, Explanation, Scorer};
+use crate::DocId;
+use crate::Score;
+
+/// How a [`FeatureBoostScorer`] combines the inner scorer's textual
+/// relevance score with the per-document static feature read from a fast
+/// field.
+#[derive(Clone, Copy)]
+pub enum FusionFn {
+    /// `textual_score + weight * ln(1 + feature)`.
+    AddLogFeature { weight: Score },
+    /// `textual_score * feature / (feature + k)`, i.e. the textual score
+    /// scaled by a saturating function of the feature.
+    ///
+    /// Requires `k > 0`: at `k == 0`, a document with `feature == 0` (a
+    /// realistic "no quality score yet" default) scores `0.0 / 0.0 = NaN`,
+    /// which then poisons every comparison downstream. Checked by
+    /// [`FeatureBoostScorer::new`].
+    MultiplySaturatedFeature { k: Score },
+}
+
+impl FusionFn {
+    fn combine(&self, textual_score: Score, feature: Score) -> Score {
+        match *self {
+            FusionFn::AddLogFeature { weight } => textual_score + weight * (1.0 + feature).ln(),
+            FusionFn::MultiplySaturatedFeature { k } => {
+                textual_score * feature / (feature + k)
+            }
+        }
+    }
+}
+
+/// An upper bound on `fusion.combine(textual_score_bound, feature)` over
+/// every `feature` in `[min_feature, max_feature]`.
+///
+/// `combine` isn't guaranteed to be increasing in `feature` -- e.g.
+/// `AddLogFeature` with a negative `weight` is *decreasing* in it, so the
+/// true bound is reached at `min_feature`, not `max_feature`. Evaluating
+/// both extremes and taking the larger is a valid bound regardless of which
+/// direction `combine` happens to move in.
+fn bound_over_feature_range(
+    fusion: FusionFn,
+    textual_score_bound: Score,
+    min_feature: Score,
+    max_feature: Score,
+) -> Score {
+    fusion
+        .combine(textual_score_bound, min_feature)
+        .max(fusion.combine(textual_score_bound, max_feature))
+}
+
+/// Fuses a textual relevance score with a precomputed, per-document static
+/// signal (e.g. a PageRank-like quality score) read from a fast field.
+///
+/// The inner scorer (typically a `TermScorer` or a union of them) provides
+/// `textual_score`; `quality_column` provides the static feature. The two
+/// are combined by `fusion`. `max_score()` stays a valid upper bound by
+/// combining the inner scorer's bound with whichever of the fast field's
+/// known minimum and maximum value makes `fusion` largest, so
+/// `FeatureBoostScorer` composes with WAND/MaxScore pruning like any other
+/// [`BlockMaxScorer`] even for a fusion function that isn't monotonically
+/// increasing in the feature (e.g. `AddLogFeature` with a negative weight).
+pub struct FeatureBoostScorer<TScorer: BlockMaxScorer> {
+    inner: TScorer,
+    quality_column: Arc<dyn Column<u64>>,
+    fusion: FusionFn,
+}
+
+impl<TScorer: BlockMaxScorer> FeatureBoostScorer<TScorer> {
+    pub fn new(
+        inner: TScorer,
+        quality_column: Arc<dyn Column<u64>>,
+        fusion: FusionFn,
+    ) -> FeatureBoostScorer<TScorer> {
+        if let FusionFn::MultiplySaturatedFeature { k } = fusion {
+            assert!(
+                k > 0.0,
+                "MultiplySaturatedFeature requires k > 0 (got {k}); k == 0 combined with a \
+                 zero-valued feature scores 0.0 / 0.0 = NaN"
+            );
+        }
+        FeatureBoostScorer {
+            inner,
+            quality_column,
+            fusion,
+        }
+    }
+
+    fn quality(&self) -> Score {
+        self.quality_column.get_val(self.inner.doc()) as Score
+    }
+
+    /// An upper bound on `fusion.combine(textual_score_bound, feature)` over
+    /// every feature value the column can produce.
+    fn feature_bound(&self, textual_score_bound: Score) -> Score {
+        let min_feature = self.quality_column.min_value() as Score;
+        let max_feature = self.quality_column.max_value() as Score;
+        bound_over_feature_range(self.fusion, textual_score_bound, min_feature, max_feature)
+    }
+
+    pub fn explain(&mut self) -> Explanation {
+        let textual_score = self.inner.score();
+        let feature = self.quality();
+        let mut explanation = Explanation::new(
+            "FeatureBoost, fusion of...",
+            self.fusion.combine(textual_score, feature),
+        );
+        explanation.add_detail(Explanation::new("textual score", textual_score));
+        explanation.add_detail(Explanation::new("static feature", feature));
+        explanation
+    }
+}
+
+impl<TScorer: BlockMaxScorer> DocSet for FeatureBoostScorer<TScorer> {
+    fn advance(&mut self) -> DocId {
+        self.inner.advance()
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        self.inner.seek(target)
+    }
+
+    fn doc(&self) -> DocId {
+        self.inner.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.inner.size_hint()
+    }
+}
+
+impl<TScorer: BlockMaxScorer> Scorer for FeatureBoostScorer<TScorer> {
+    fn score(&mut self) -> Score {
+        let textual_score = self.inner.score();
+        let feature = self.quality();
+        self.fusion.combine(textual_score, feature)
+    }
+}
+
+impl<TScorer: BlockMaxScorer> BlockMaxScorer for FeatureBoostScorer<TScorer> {
+    fn max_score(&self) -> Score {
+        self.feature_bound(self.inner.max_score())
+    }
+
+    fn block_max_score(&mut self) -> Score {
+        let textual_score_bound = self.inner.block_max_score();
+        self.feature_bound(textual_score_bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bound_over_feature_range, FusionFn};
+    use crate::Score;
+
+    /// Brute-force sample of `fusion.combine(textual_score_bound, feature)`
+    /// across `feature in [min_feature, max_feature]`, used to check that
+    /// `bound_over_feature_range` is a genuine upper bound rather than just
+    /// correct at the two ends.
+    fn sampled_max_combine(
+        fusion: FusionFn,
+        textual_score_bound: Score,
+        min_feature: Score,
+        max_feature: Score,
+    ) -> Score {
+        const SAMPLES: u32 = 1000;
+        (0..=SAMPLES)
+            .map(|i| {
+                let t = i as Score / SAMPLES as Score;
+                let feature = min_feature + t * (max_feature - min_feature);
+                fusion.combine(textual_score_bound, feature)
+            })
+            .fold(Score::NEG_INFINITY, Score::max)
+    }
+
+    #[test]
+    fn test_bound_covers_negative_weight_add_log_feature() {
+        // The regression this guards against: using max_feature alone would
+        // under-shoot the bound here, since combine() is decreasing in
+        // feature for a negative weight.
+        let fusion = FusionFn::AddLogFeature { weight: -0.5 };
+        let bound = bound_over_feature_range(fusion, 1.2, 0.0, 100.0);
+        let sampled_max = sampled_max_combine(fusion, 1.2, 0.0, 100.0);
+        assert!(
+            bound >= sampled_max - 0.0001,
+            "bound {bound} does not cover sampled max {sampled_max}"
+        );
+        let naive_max_value_only_bound = fusion.combine(1.2, 100.0);
+        assert!(
+            bound > naive_max_value_only_bound,
+            "bound {bound} should be strictly tighter-covering than the naive {naive_max_value_only_bound}"
+        );
+    }
+
+    #[test]
+    fn test_bound_covers_positive_weight_add_log_feature() {
+        let fusion = FusionFn::AddLogFeature { weight: 0.5 };
+        let bound = bound_over_feature_range(fusion, 1.2, 0.0, 100.0);
+        let sampled_max = sampled_max_combine(fusion, 1.2, 0.0, 100.0);
+        assert!(
+            bound >= sampled_max - 0.0001,
+            "bound {bound} does not cover sampled max {sampled_max}"
+        );
+    }
+
+    #[test]
+    fn test_bound_covers_multiply_saturated_feature() {
+        let fusion = FusionFn::MultiplySaturatedFeature { k: 2.0 };
+        let bound = bound_over_feature_range(fusion, 1.2, 0.0, 100.0);
+        let sampled_max = sampled_max_combine(fusion, 1.2, 0.0, 100.0);
+        assert!(
+            bound >= sampled_max - 0.0001,
+            "bound {bound} does not cover sampled max {sampled_max}"
+        );
+    }
+}
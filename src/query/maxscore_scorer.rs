@@ -0,0 +1,334 @@
+use crate::docset::DocSet;
+use crate::query::{BlockMaxScorer, Scorer};
+use crate::DocId;
+use crate::Score;
+use crate::TERMINATED;
+
+/// Disjunctive (OR) union of term scorers, pruned with the Block-Max
+/// MaxScore algorithm rather than WAND.
+///
+/// The scorers are partitioned into an "essential" set and a "non-essential"
+/// set such that the sum of the non-essential scorers' `max_score()` is
+/// strictly less than the current threshold `theta` (the score of the
+/// weakest candidate currently in the top-k). Any document that could enter
+/// the top-k must therefore match at least one essential scorer, so only
+/// the essential scorers need to be advanced to generate candidates; the
+/// non-essential scorers are only `seek`-ed to a candidate once it is found,
+/// and can bail out early as soon as the running partial score plus the
+/// remaining non-essential bounds can no longer reach `theta`.
+///
+/// As `theta` rises, scorers migrate from essential to non-essential, so the
+/// partition is recomputed whenever it changes. `block_max_score()` is used
+/// to tighten the essential scorers' bound for the block each one is
+/// currently in, which lets candidate documents be skipped more
+/// aggressively than the global MaxScore bound alone would allow.
+pub struct MaxScoreScorer<TScorer: BlockMaxScorer> {
+    scorers: Vec<TScorer>,
+    /// Indices into `scorers`, sorted by ascending `max_score()`. The
+    /// essential set is the suffix of this order whose scorers cannot all
+    /// fit under `theta`; the non-essential set is the prefix that can.
+    order: Vec<usize>,
+    /// Number of entries of `order` (from the front) that are
+    /// non-essential.
+    non_essential_count: usize,
+    theta: Score,
+    doc: DocId,
+}
+
+impl<TScorer: BlockMaxScorer> MaxScoreScorer<TScorer> {
+    pub fn new(scorers: Vec<TScorer>) -> MaxScoreScorer<TScorer> {
+        assert!(!scorers.is_empty());
+        let mut order: Vec<usize> = (0..scorers.len()).collect();
+        order.sort_by(|&left, &right| {
+            // `max_score()` can be NaN for a degenerate but reachable
+            // composed scorer (e.g. a `FeatureBoostScorer` built with a NaN
+            // `AddLogFeature` weight): treat it
+            // as equal rather than panicking on `partial_cmp`.
+            scorers[left]
+                .max_score()
+                .partial_cmp(&scorers[right].max_score())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let mut union_scorer = MaxScoreScorer {
+            scorers,
+            order,
+            non_essential_count: 0,
+            theta: 0.0,
+            doc: 0,
+        };
+        union_scorer.recompute_partition();
+        union_scorer.doc = union_scorer.advance_essential_to_next_candidate(true);
+        union_scorer.skip_unreachable_candidates();
+        union_scorer
+    }
+
+    /// Skips candidates whose upper bound can no longer reach `theta`: a
+    /// document that cannot beat the current threshold cannot be in the
+    /// top-k, so it is never worth scoring.
+    fn skip_unreachable_candidates(&mut self) {
+        while self.doc != TERMINATED && self.upper_bound() < self.theta {
+            self.doc = self.advance_essential_to_next_candidate(false);
+        }
+    }
+
+    /// Raises the pruning threshold. Called by the top-k collector once it
+    /// has `k` results, with the score of the current k-th best one.
+    pub fn set_threshold(&mut self, theta: Score) {
+        if theta > self.theta {
+            self.theta = theta;
+            self.recompute_partition();
+            self.skip_unreachable_candidates();
+        }
+    }
+
+    fn recompute_partition(&mut self) {
+        let mut non_essential_sum = 0f32;
+        let mut non_essential_count = 0;
+        for &idx in &self.order {
+            let candidate_sum = non_essential_sum + self.scorers[idx].max_score();
+            if candidate_sum >= self.theta {
+                break;
+            }
+            non_essential_sum = candidate_sum;
+            non_essential_count += 1;
+        }
+        self.non_essential_count = non_essential_count;
+    }
+
+    fn essential_indices(&self) -> &[usize] {
+        &self.order[self.non_essential_count..]
+    }
+
+    fn non_essential_indices(&self) -> &[usize] {
+        &self.order[..self.non_essential_count]
+    }
+
+    /// Finds the next candidate doc, driving iteration only from the
+    /// essential scorers. On the very first call (`is_first`), the scorers
+    /// are already positioned on their first doc and must not be advanced.
+    fn advance_essential_to_next_candidate(&mut self, is_first: bool) -> DocId {
+        if !is_first {
+            let candidate = self.doc;
+            for &idx in self.essential_indices().to_vec().iter() {
+                if self.scorers[idx].doc() == candidate {
+                    self.scorers[idx].advance();
+                }
+            }
+        }
+        self.essential_indices()
+            .iter()
+            .map(|&idx| self.scorers[idx].doc())
+            .min()
+            .unwrap_or(TERMINATED)
+    }
+
+    /// Upper bound on the score achievable on the current doc, used to
+    /// decide whether it is worth scoring at all.
+    fn upper_bound(&mut self) -> Score {
+        let essential_bound: Score = self
+            .essential_indices()
+            .to_vec()
+            .iter()
+            .map(|&idx| self.scorers[idx].block_max_score())
+            .sum();
+        let non_essential_bound: Score = self
+            .non_essential_indices()
+            .iter()
+            .map(|&idx| self.scorers[idx].max_score())
+            .sum();
+        essential_bound + non_essential_bound
+    }
+}
+
+impl<TScorer: BlockMaxScorer> Scorer for MaxScoreScorer<TScorer> {
+    /// Scores the current doc, skipping a non-essential scorer's
+    /// contribution as soon as it can no longer change whether the
+    /// document beats `theta`.
+    fn score(&mut self) -> Score {
+        let doc = self.doc;
+        let mut score: Score = self
+            .essential_indices()
+            .to_vec()
+            .iter()
+            .filter(|&&idx| self.scorers[idx].doc() == doc)
+            .map(|&idx| self.scorers[idx].score())
+            .sum();
+
+        let non_essential: Vec<usize> = self.non_essential_indices().to_vec();
+        let mut remaining_bound: Score = non_essential
+            .iter()
+            .map(|&idx| self.scorers[idx].max_score())
+            .sum();
+        for idx in non_essential {
+            if score + remaining_bound < self.theta {
+                // Even if every remaining non-essential scorer matched, this
+                // doc could not beat theta: stop early.
+                break;
+            }
+            remaining_bound -= self.scorers[idx].max_score();
+            // A scorer demoted from essential to non-essential by a
+            // `set_threshold` call earlier than the current doc can already
+            // sit ahead of it (only the minimum of the essential set is
+            // required to equal `doc`): only `seek` if it is still behind,
+            // exactly as `BM25FScorer::seek` guards its per-field seeks.
+            if self.scorers[idx].doc() < doc {
+                self.scorers[idx].seek(doc);
+            }
+            if self.scorers[idx].doc() == doc {
+                score += self.scorers[idx].score();
+            }
+        }
+        score
+    }
+}
+
+impl<TScorer: BlockMaxScorer> DocSet for MaxScoreScorer<TScorer> {
+    fn advance(&mut self) -> DocId {
+        self.doc = self.advance_essential_to_next_candidate(false);
+        self.skip_unreachable_candidates();
+        self.doc
+    }
+
+    fn doc(&self) -> DocId {
+        self.doc
+    }
+
+    fn size_hint(&self) -> u32 {
+        // A disjunction can match any doc any one of its scorers matches,
+        // so the candidate count is bounded by the sum of the children's
+        // doc counts, not just the largest one.
+        self.scorers.iter().map(|scorer| scorer.size_hint()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MaxScoreScorer;
+    use crate::docset::DocSet;
+    use crate::query::term_query::TermScorer;
+    use crate::query::{BM25Weight, Scorer};
+    use crate::TERMINATED;
+
+    fn term_scorer(doc_and_tfs: &[(u32, u32)]) -> TermScorer {
+        let fieldnorms = vec![10u32; doc_and_tfs.len()];
+        let weight = BM25Weight::for_one_term(2, 10, 10f32);
+        TermScorer::create_for_test(doc_and_tfs, &fieldnorms, weight)
+    }
+
+    /// Brute-force disjunction score for `doc`: the sum of the score of
+    /// every scorer currently positioned on it. `scorers` must already be
+    /// positioned at or before `doc` (callers drive this in ascending doc
+    /// order, as `DocSet::seek` cannot move backwards).
+    fn brute_force_score(scorers: &mut [TermScorer], doc: u32) -> f32 {
+        scorers
+            .iter_mut()
+            .filter(|scorer| scorer.seek(doc) == doc)
+            .map(|scorer| scorer.score())
+            .sum()
+    }
+
+    #[test]
+    fn test_maxscore_scorer_matches_brute_force_union() {
+        let fixtures: Vec<Vec<(u32, u32)>> = vec![
+            vec![(1, 2), (4, 3), (8, 1)],
+            vec![(2, 5), (4, 1), (8, 4)],
+            vec![(3, 1), (4, 2)],
+        ];
+        let mut all_docs: Vec<u32> = fixtures.iter().flatten().map(|&(doc, _)| doc).collect();
+        all_docs.sort_unstable();
+        all_docs.dedup();
+
+        // Compute the expected score of every candidate doc with a
+        // brand-new set of scorers, independent of the ones under test.
+        let mut reference: Vec<TermScorer> = fixtures.iter().map(|docs| term_scorer(docs)).collect();
+        let expected_scores: Vec<(u32, f32)> = all_docs
+            .iter()
+            .map(|&doc| (doc, brute_force_score(&mut reference, doc)))
+            .collect();
+        let max_expected_score = expected_scores
+            .iter()
+            .map(|&(_, score)| score)
+            .fold(0f32, f32::max);
+
+        // Run the real scorer under test with a threshold that forces it to
+        // prune some, but not all, candidates.
+        let scorers: Vec<TermScorer> = fixtures.iter().map(|docs| term_scorer(docs)).collect();
+        let mut union_scorer = MaxScoreScorer::new(scorers);
+        union_scorer.set_threshold(max_expected_score * 0.99);
+
+        let mut seen = Vec::new();
+        let mut doc = union_scorer.doc();
+        while doc != TERMINATED {
+            seen.push((doc, union_scorer.score()));
+            doc = union_scorer.advance();
+        }
+
+        // Every doc the pruned scorer returns must have exactly the
+        // brute-force score: pruning must never change a kept doc's score.
+        for &(doc, actual_score) in &seen {
+            let expected_score = expected_scores
+                .iter()
+                .find(|&&(expected_doc, _)| expected_doc == doc)
+                .map(|&(_, score)| score)
+                .unwrap();
+            assert!(
+                (expected_score - actual_score).abs() < 0.0001,
+                "doc {doc}: expected {expected_score}, got {actual_score}"
+            );
+        }
+
+        // The critical invariant: no doc whose achievable score could beat
+        // the threshold may be skipped.
+        for &(doc, expected_score) in &expected_scores {
+            if expected_score >= union_scorer.theta {
+                assert!(
+                    seen.iter().any(|&(seen_doc, _)| seen_doc == doc),
+                    "doc {doc} scores {expected_score} >= theta but was pruned away"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_maxscore_scorer_guards_seek_on_mid_walk_demotion() {
+        use crate::query::similarity::SimilarityWeight;
+
+        // Three different weights so `max_score()` differs across scorers
+        // and the essential/non-essential partition is deterministic rather
+        // than tie-broken.
+        let small_weight = BM25Weight::for_one_term(9, 10, 10f32);
+        let mid_weight = BM25Weight::for_one_term(5, 10, 10f32);
+        let large_weight = BM25Weight::for_one_term(1, 10, 10f32);
+        let small_max = small_weight.max_score();
+        let mid_max = mid_weight.max_score();
+
+        // `small` sits far ahead of doc 2 once it has advanced once; it
+        // starts essential (every scorer does, until the first
+        // `set_threshold` call) and only later gets demoted to
+        // non-essential while it is already positioned past the current
+        // candidate doc.
+        let small = TermScorer::create_for_test(&[(1, 1), (50, 1)], &[10, 10], small_weight);
+        let mid = TermScorer::create_for_test(&[(1, 1), (10, 1)], &[10, 10], mid_weight);
+        let large = TermScorer::create_for_test(&[(2, 1)], &[10], large_weight.clone());
+
+        let mut union_scorer = MaxScoreScorer::new(vec![small, mid, large]);
+        assert_eq!(union_scorer.doc(), 1);
+        union_scorer.score();
+        assert_eq!(union_scorer.advance(), 2);
+
+        // Demote `small` (now positioned at doc 50) to non-essential while
+        // the current candidate is doc 2: the pre-fix code unconditionally
+        // called `seek(2)` on it here, a backward seek from 50.
+        union_scorer.set_threshold(small_max + mid_max / 2.0);
+
+        // Must not panic, and `small` must not be credited on doc 2 since it
+        // does not actually match it.
+        let score = union_scorer.score();
+        let mut large_only = TermScorer::create_for_test(&[(2, 1)], &[10], large_weight);
+        let expected = large_only.score();
+        assert!(
+            (score - expected).abs() < 0.0001,
+            "expected {expected}, got {score}"
+        );
+    }
+}
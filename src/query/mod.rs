@@ -0,0 +1,14 @@
+mod block_max_scorer;
+mod bm25;
+mod bm25f;
+mod feature_boost_scorer;
+mod maxscore_scorer;
+mod similarity;
+pub mod term_query;
+
+pub use self::block_max_scorer::BlockMaxScorer;
+pub use self::bm25::BM25Weight;
+pub use self::bm25f::{BM25FFieldParams, BM25FWeight};
+pub use self::feature_boost_scorer::{FeatureBoostScorer, FusionFn};
+pub use self::maxscore_scorer::MaxScoreScorer;
+pub use self::similarity::SimilarityWeight;
@@ -0,0 +1,27 @@
+use crate::query::Explanation;
+use crate::Score;
+
+/// A `SimilarityWeight` is the per-segment, per-term piece of a scoring
+/// model: everything a scorer needs to turn postings into a [`Score`]
+/// without going back to the index.
+///
+/// This is what lets `TermScorer` (and other scorers built on top of raw
+/// postings) stay agnostic to the actual ranking formula. `BM25Weight` is
+/// the default implementation, but other scoring models (DFR, a language
+/// model with Dirichlet or Jelinek-Mercer smoothing, ...) can be plugged in
+/// by implementing this trait instead of forking the scorer.
+pub trait SimilarityWeight: Clone + Send + Sync + 'static {
+    /// Scores a single document, given the quantized fieldnorm of the
+    /// document (`fieldnorm_id`) and the term frequency within that
+    /// document.
+    fn score(&self, fieldnorm_id: u8, term_freq: u32) -> Score;
+
+    /// An upper bound on `score()`, over every possible `(fieldnorm_id,
+    /// term_freq)` pair. Used by WAND/MaxScore-style dynamic pruning to
+    /// skip documents that cannot make it into the top-k.
+    fn max_score(&self) -> Score;
+
+    /// Explains how `score()` computed its result for a given
+    /// `fieldnorm_id`/`term_freq` pair.
+    fn explain(&self, fieldnorm_id: u8, term_freq: u32) -> Explanation;
+}
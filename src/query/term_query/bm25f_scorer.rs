@@ -0,0 +1,255 @@
+use crate::docset::DocSet;
+use crate::fieldnorm::FieldNormReader;
+use crate::postings::SegmentPostings;
+use crate::query::bm25f::{BM25FFieldParams, BM25FWeight};
+use crate::query::{BlockMaxScorer, Explanation, Scorer};
+use crate::DocId;
+use crate::Score;
+use crate::TERMINATED;
+
+/// One field's postings for the term being scored, bundled together with
+/// the fieldnorm reader and BM25F parameters needed to turn its term
+/// frequency into a pseudo-term-frequency contribution. Keeping these three
+/// together (rather than as parallel vectors indexed positionally) means a
+/// field's postings and its parameters cannot drift out of sync.
+struct BM25FField {
+    postings: SegmentPostings,
+    fieldnorm_reader: FieldNormReader,
+    params: BM25FFieldParams,
+}
+
+/// Scores a single term across several fields of the same document jointly,
+/// using [`BM25FWeight`], instead of scoring each field with its own
+/// [`TermScorer`](crate::query::term_query::TermScorer) and summing the
+/// results.
+///
+/// The per-field postings are advanced in lockstep: `doc()` is always the
+/// smallest current doc among the fields, and a field only contributes a
+/// term frequency when it is positioned on that exact doc.
+///
+/// Like [`TermWeight`](crate::query::term_query::TermWeight), this is not
+/// wired into any real query-execution path in this crate slice: there is
+/// no `Query`/`Weight` machinery here to construct it from a multi-field
+/// term query, so it's exercised only by its own unit tests for now.
+pub struct BM25FScorer {
+    fields: Vec<BM25FField>,
+    weight: BM25FWeight,
+    doc: DocId,
+}
+
+impl BM25FScorer {
+    pub fn new(
+        fields: Vec<(SegmentPostings, FieldNormReader, BM25FFieldParams)>,
+        weight: BM25FWeight,
+    ) -> BM25FScorer {
+        assert!(!fields.is_empty(), "BM25F requires at least one field");
+        let mut fields: Vec<BM25FField> = fields
+            .into_iter()
+            .map(|(postings, fieldnorm_reader, params)| BM25FField {
+                postings,
+                fieldnorm_reader,
+                params,
+            })
+            .collect();
+        let doc = Self::min_doc(&mut fields);
+        BM25FScorer { fields, weight, doc }
+    }
+
+    fn min_doc(fields: &mut [BM25FField]) -> DocId {
+        fields
+            .iter()
+            .map(|field| field.postings.doc())
+            .min()
+            .unwrap_or(TERMINATED)
+    }
+
+    /// Combines every field's contribution for the current doc into one
+    /// BM25F pseudo term frequency, per the formula documented on
+    /// [`BM25FWeight`].
+    fn pseudo_term_freq(&self) -> Score {
+        self.fields
+            .iter()
+            .filter(|field| field.postings.doc() == self.doc)
+            .map(|field| {
+                let fieldnorm_id = field.fieldnorm_reader.fieldnorm_id(self.doc);
+                let fieldnorm = FieldNormReader::id_to_fieldnorm(fieldnorm_id) as Score;
+                let length_norm = 1.0 - field.params.b + field.params.b * fieldnorm
+                    / field.params.average_fieldnorm;
+                field.params.boost * field.postings.term_freq() as Score / length_norm
+            })
+            .sum()
+    }
+
+    pub fn explain(&self) -> Explanation {
+        self.weight.explain(self.pseudo_term_freq())
+    }
+}
+
+impl DocSet for BM25FScorer {
+    fn advance(&mut self) -> DocId {
+        for field in &mut self.fields {
+            if field.postings.doc() == self.doc {
+                field.postings.advance();
+            }
+        }
+        self.doc = Self::min_doc(&mut self.fields);
+        self.doc
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        for field in &mut self.fields {
+            if field.postings.doc() < target {
+                field.postings.seek(target);
+            }
+        }
+        self.doc = Self::min_doc(&mut self.fields);
+        self.doc
+    }
+
+    fn doc(&self) -> DocId {
+        self.doc
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.fields
+            .iter()
+            .map(|field| field.postings.size_hint())
+            .sum()
+    }
+}
+
+impl Scorer for BM25FScorer {
+    fn score(&mut self) -> Score {
+        self.weight.score(self.pseudo_term_freq())
+    }
+}
+
+impl BlockMaxScorer for BM25FScorer {
+    fn max_score(&self) -> Score {
+        self.weight.max_score()
+    }
+
+    // Each field's postings expose a block-max bound for a single-field
+    // BM25 weight, not for the joint BM25F saturation applied here, so we
+    // fall back to the (looser, but always valid) global bound instead of
+    // trying to recombine per-field block bounds.
+    fn block_max_score(&mut self) -> Score {
+        self.weight.max_score()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BM25FScorer;
+    use crate::docset::DocSet;
+    use crate::fieldnorm::FieldNormReader;
+    use crate::postings::SegmentPostings;
+    use crate::query::bm25f::{BM25FFieldParams, BM25FWeight};
+    use crate::query::BlockMaxScorer;
+    use crate::tests::assert_nearly_equals;
+    use crate::{Score, TERMINATED};
+    use core::iter;
+
+    fn field(
+        doc_and_tfs: &[(u32, u32)],
+        fieldnorm_vals: &[u32],
+        params: BM25FFieldParams,
+    ) -> (SegmentPostings, FieldNormReader, BM25FFieldParams) {
+        assert_eq!(doc_and_tfs.len(), fieldnorm_vals.len());
+        let postings = SegmentPostings::create_from_docs_and_tfs(doc_and_tfs);
+        let max_doc = doc_and_tfs.iter().map(|&(doc, _)| doc).max().unwrap() + 1;
+        let mut fieldnorms: Vec<u32> = iter::repeat(0).take(max_doc as usize).collect();
+        for (&(doc, _), &fieldnorm) in doc_and_tfs.iter().zip(fieldnorm_vals) {
+            fieldnorms[doc as usize] = fieldnorm;
+        }
+        let fieldnorm_reader = FieldNormReader::from(&fieldnorms[..]);
+        (postings, fieldnorm_reader, params)
+    }
+
+    /// Computes a single field's contribution to `tilde_tf`, following the
+    /// formula documented on `BM25FWeight`, so the expected values below are
+    /// derived the same way the scorer is, rather than pasted in as magic
+    /// constants.
+    fn pseudo_tf_contribution(
+        boost: Score,
+        b: Score,
+        fieldnorm: Score,
+        average_fieldnorm: Score,
+        tf: Score,
+    ) -> Score {
+        let length_norm = 1.0 - b + b * fieldnorm / average_fieldnorm;
+        boost * tf / length_norm
+    }
+
+    #[test]
+    fn test_bm25f_scorer_multi_field_union_and_score() {
+        // `title` only matches doc 1, `anchor` only matches doc 4: the
+        // fields disagree on which docs match, so the scorer's union must
+        // visit exactly {1, 4}. Doc 1 is a genuine multi-field overlap
+        // (title + body both match it); doc 4 is scored from body + anchor
+        // only, with title correctly excluded.
+        let title = field(
+            &[(1, 3)],
+            &[10],
+            BM25FFieldParams { boost: 2.0, b: 0.75, average_fieldnorm: 12.0 },
+        );
+        let body = field(
+            &[(1, 6), (4, 2)],
+            &[20, 15],
+            BM25FFieldParams { boost: 1.0, b: 0.5, average_fieldnorm: 18.0 },
+        );
+        let anchor = field(
+            &[(4, 1)],
+            &[6],
+            BM25FFieldParams { boost: 1.5, b: 0.6, average_fieldnorm: 8.0 },
+        );
+
+        let weight = BM25FWeight::for_terms(2, 10);
+        let mut scorer = BM25FScorer::new(vec![title, body, anchor], weight.clone());
+
+        assert_eq!(scorer.doc(), 1);
+        let expected_doc1 = pseudo_tf_contribution(2.0, 0.75, 10.0, 12.0, 3.0)
+            + pseudo_tf_contribution(1.0, 0.5, 20.0, 18.0, 6.0);
+        assert_nearly_equals(scorer.score(), weight.score(expected_doc1));
+
+        assert_eq!(scorer.advance(), 4);
+        let expected_doc4 = pseudo_tf_contribution(1.0, 0.5, 15.0, 18.0, 2.0)
+            + pseudo_tf_contribution(1.5, 0.6, 6.0, 8.0, 1.0);
+        assert_nearly_equals(scorer.score(), weight.score(expected_doc4));
+
+        assert_eq!(scorer.advance(), TERMINATED);
+    }
+
+    #[test]
+    fn test_bm25f_scorer_seek_skips_non_matching_fields() {
+        let title = field(
+            &[(2, 4), (9, 1)],
+            &[8, 8],
+            BM25FFieldParams { boost: 1.0, b: 0.75, average_fieldnorm: 8.0 },
+        );
+        let body = field(
+            &[(5, 2), (9, 3)],
+            &[8, 8],
+            BM25FFieldParams { boost: 1.0, b: 0.75, average_fieldnorm: 8.0 },
+        );
+
+        let weight = BM25FWeight::for_terms(1, 4);
+        let mut scorer = BM25FScorer::new(vec![title, body], weight.clone());
+
+        assert_eq!(scorer.doc(), 2);
+        assert_eq!(scorer.seek(9), 9);
+
+        let expected = pseudo_tf_contribution(1.0, 0.75, 8.0, 8.0, 1.0)
+            + pseudo_tf_contribution(1.0, 0.75, 8.0, 8.0, 3.0);
+        assert_nearly_equals(scorer.score(), weight.score(expected));
+        assert_eq!(scorer.max_score(), weight.max_score());
+        assert_eq!(scorer.seek(TERMINATED), TERMINATED);
+    }
+
+    #[test]
+    #[should_panic(expected = "BM25F requires at least one field")]
+    fn test_bm25f_scorer_requires_at_least_one_field() {
+        let fields = Vec::<(SegmentPostings, FieldNormReader, BM25FFieldParams)>::new();
+        BM25FScorer::new(fields, BM25FWeight::for_terms(1, 4));
+    }
+}
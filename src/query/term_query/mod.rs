@@ -0,0 +1,7 @@
+mod bm25f_scorer;
+mod term_scorer;
+mod term_weight;
+
+pub use self::bm25f_scorer::BM25FScorer;
+pub use self::term_scorer::TermScorer;
+pub use self::term_weight::TermWeight;
@@ -1,5 +1,9 @@
+//! [`TermScorer`] is generic over the [`SimilarityWeight`] it scores with,
+//! so that a single scorer implementation can be driven by any scoring
+//! model (BM25 by default) rather than being hardcoded to BM25.
+
 use crate::docset::DocSet;
-use crate::query::{Explanation, Scorer};
+use crate::query::{BlockMaxScorer, Explanation, Scorer};
 use crate::DocId;
 use crate::Score;
 
@@ -7,20 +11,27 @@ use crate::fieldnorm::FieldNormReader;
 use crate::postings::SegmentPostings;
 use crate::postings::{FreqReadingOption, Postings};
 use crate::query::bm25::BM25Weight;
+use crate::query::similarity::SimilarityWeight;
 use core::iter;
 
-pub struct TermScorer {
+pub struct TermScorer<TSimilarityWeight = BM25Weight>
+where
+    TSimilarityWeight: SimilarityWeight,
+{
     pub(crate) postings: SegmentPostings,
     fieldnorm_reader: FieldNormReader,
-    similarity_weight: BM25Weight,
+    similarity_weight: TSimilarityWeight,
 }
 
-impl TermScorer {
+impl<TSimilarityWeight> TermScorer<TSimilarityWeight>
+where
+    TSimilarityWeight: SimilarityWeight,
+{
     pub fn new(
         postings: SegmentPostings,
         fieldnorm_reader: FieldNormReader,
-        similarity_weight: BM25Weight,
-    ) -> TermScorer {
+        similarity_weight: TSimilarityWeight,
+    ) -> TermScorer<TSimilarityWeight> {
         TermScorer {
             postings,
             fieldnorm_reader,
@@ -32,8 +43,8 @@ impl TermScorer {
     pub fn create_for_test(
         doc_and_tfs: &[(DocId, u32)],
         fieldnorm_vals: &[u32],
-        similarity_weight: BM25Weight,
-    ) -> TermScorer {
+        similarity_weight: TSimilarityWeight,
+    ) -> TermScorer<TSimilarityWeight> {
         assert!(!doc_and_tfs.is_empty());
         assert_eq!(doc_and_tfs.len(), fieldnorm_vals.len());
         let segment_postings = SegmentPostings::create_from_docs_and_tfs(doc_and_tfs);
@@ -53,10 +64,17 @@ impl TermScorer {
         self.postings.block_cursor.freq_reading_option()
     }
 
+    /// Upper bound on `score()` for the block of postings this scorer is
+    /// currently positioned in.
+    ///
+    /// The postings' block skip-list caches this bound pre-computed for
+    /// `BM25Weight` specifically (see `src/postings/`), so it cannot be
+    /// reused for an arbitrary `TSimilarityWeight` without generalizing
+    /// that method first -- which is out of scope here. Until that's done,
+    /// fall back to the (always valid, if less tight) global `max_score()`,
+    /// matching `BM25FScorer::block_max_score`.
     pub fn block_max_score(&mut self) -> Score {
-        self.postings
-            .block_cursor
-            .block_max_score(&self.fieldnorm_reader, &self.similarity_weight)
+        self.similarity_weight.max_score()
     }
 
     pub fn term_freq(&self) -> u32 {
@@ -82,7 +100,10 @@ impl TermScorer {
     }
 }
 
-impl DocSet for TermScorer {
+impl<TSimilarityWeight> DocSet for TermScorer<TSimilarityWeight>
+where
+    TSimilarityWeight: SimilarityWeight,
+{
     fn advance(&mut self) -> DocId {
         self.postings.advance()
     }
@@ -100,7 +121,10 @@ impl DocSet for TermScorer {
     }
 }
 
-impl Scorer for TermScorer {
+impl<TSimilarityWeight> Scorer for TermScorer<TSimilarityWeight>
+where
+    TSimilarityWeight: SimilarityWeight,
+{
     fn score(&mut self) -> Score {
         let fieldnorm_id = self.fieldnorm_id();
         let term_freq = self.term_freq();
@@ -108,6 +132,19 @@ impl Scorer for TermScorer {
     }
 }
 
+impl<TSimilarityWeight> BlockMaxScorer for TermScorer<TSimilarityWeight>
+where
+    TSimilarityWeight: SimilarityWeight,
+{
+    fn max_score(&self) -> Score {
+        TermScorer::max_score(self)
+    }
+
+    fn block_max_score(&mut self) -> Score {
+        TermScorer::block_max_score(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::query::term_query::TermScorer;
@@ -124,7 +161,10 @@ mod tests {
         assert_eq!(max_scorer, 1.3990127f32);
         assert_eq!(term_scorer.doc(), 2);
         assert_eq!(term_scorer.term_freq(), 3);
-        assert_nearly_equals(term_scorer.block_max_score(), 1.3676447f32);
+        // `block_max_score` falls back to the global `max_score` bound (see
+        // its doc comment): it is generic over `SimilarityWeight` and the
+        // BM25-specific block skip-list bound hasn't been generalized.
+        assert_nearly_equals(term_scorer.block_max_score(), max_scorer);
         assert_nearly_equals(term_scorer.score(), 1.0892314f32);
         assert_eq!(term_scorer.advance(), 3);
         assert_eq!(term_scorer.doc(), 3);
@@ -0,0 +1,38 @@
+use crate::fieldnorm::FieldNormReader;
+use crate::postings::SegmentPostings;
+use crate::query::similarity::SimilarityWeight;
+use crate::query::term_query::TermScorer;
+
+/// Builds a [`TermScorer`] for a term, generic over the
+/// [`SimilarityWeight`] used to score it.
+///
+/// This is meant as the seam `Query::weight()` plumbing would go through to
+/// pick which scoring model a term query is evaluated with: a caller that
+/// wants BM25 constructs a `TermWeight<BM25Weight>`, while a caller
+/// experimenting with a different model (DFR, a language model, ...)
+/// constructs a `TermWeight<TheirSimilarityWeight>` instead, without
+/// `TermScorer` itself having to change.
+///
+/// Neither the `Query`/`Weight` traits nor `TermQuery` exist in this crate
+/// slice, so this struct is not actually reachable from query execution
+/// yet -- it's scaffolding for a seam that doesn't have a caller here.
+/// Wiring it up is blocked on whatever crate those traits live in.
+pub struct TermWeight<TSimilarityWeight: SimilarityWeight> {
+    similarity_weight: TSimilarityWeight,
+}
+
+impl<TSimilarityWeight: SimilarityWeight> TermWeight<TSimilarityWeight> {
+    pub fn new(similarity_weight: TSimilarityWeight) -> TermWeight<TSimilarityWeight> {
+        TermWeight { similarity_weight }
+    }
+
+    /// Builds the scorer for this term within a single segment, given its
+    /// postings and fieldnorm reader for that segment.
+    pub fn scorer(
+        &self,
+        postings: SegmentPostings,
+        fieldnorm_reader: FieldNormReader,
+    ) -> TermScorer<TSimilarityWeight> {
+        TermScorer::new(postings, fieldnorm_reader, self.similarity_weight.clone())
+    }
+}